@@ -0,0 +1,351 @@
+//! Decoding of AIS `!AIVDM`/`!AIVDO` sentences for vessel tracking.
+//!
+//! AIS payloads are 6-bit ASCII-armored bitstreams that can span several
+//! NMEA sentences (fragments). [`AisDecoder`] reassembles the fragments of
+//! one message and decodes it once complete.
+
+use crate::gps_data::verify_checksum;
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+/// A decoded AIS message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AisMessage {
+    /// Message types 1-3: a Class A position report.
+    PositionReport(AisPositionReport),
+    /// Message type 5: static and voyage-related vessel data.
+    StaticData(AisStaticData),
+}
+
+/// A Class A position report (AIS message types 1, 2 and 3).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AisPositionReport {
+    /// Which of the three position-report message types this was.
+    pub message_type: u8,
+    /// Maritime Mobile Service Identity of the reporting vessel.
+    pub mmsi: u32,
+    /// Latitude in decimal degrees, `None` if not available (value 0x3412140 / 91 degrees).
+    pub latitude: Option<f64>,
+    /// Longitude in decimal degrees, `None` if not available (181 degrees).
+    pub longitude: Option<f64>,
+    /// Speed over ground, in knots. `None` if not available.
+    pub speed_over_ground: Option<f64>,
+    /// Course over ground, in degrees true. `None` if not available.
+    pub course_over_ground: Option<f64>,
+    /// True heading, in degrees. `None` if not available.
+    pub true_heading: Option<u16>,
+}
+
+/// Static and voyage-related vessel data (AIS message type 5).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AisStaticData {
+    /// Maritime Mobile Service Identity of the vessel.
+    pub mmsi: u32,
+    /// Vessel name, with trailing `@` padding trimmed.
+    pub vessel_name: String,
+    /// Ship and cargo type code, per the AIS type-code table.
+    pub ship_type: u8,
+}
+
+/// Converts one 6-bit-armored AIS payload character into its 6-bit value.
+fn unarmor_char(c: u8) -> Option<u8> {
+    let value = c.checked_sub(48)?;
+    let value = if value > 40 { value.checked_sub(8)? } else { value };
+    if value > 63 {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Unpacks an armored AIS payload into a bitstream, one `bool` per bit.
+fn unarmor_payload(payload: &str) -> Result<Vec<bool>> {
+    let mut bits = Vec::with_capacity(payload.len() * 6);
+    for c in payload.bytes() {
+        let value = unarmor_char(c).ok_or_else(|| anyhow!("invalid AIS payload character: {c}"))?;
+        for i in (0..6).rev() {
+            bits.push((value >> i) & 1 == 1);
+        }
+    }
+    Ok(bits)
+}
+
+fn read_uint(bits: &[bool], start: usize, len: usize) -> Option<u64> {
+    bits.get(start..start + len)
+        .map(|slice| slice.iter().fold(0u64, |acc, &b| (acc << 1) | b as u64))
+}
+
+fn read_int(bits: &[bool], start: usize, len: usize) -> Option<i64> {
+    let unsigned = read_uint(bits, start, len)?;
+    Some(if bits[start] {
+        unsigned as i64 - (1i64 << len)
+    } else {
+        unsigned as i64
+    })
+}
+
+/// Decodes `num_chars` six-bit ASCII characters starting at bit `start`,
+/// trimming trailing `@` padding.
+fn read_6bit_string(bits: &[bool], start: usize, num_chars: usize) -> String {
+    let mut s = String::with_capacity(num_chars);
+    for i in 0..num_chars {
+        let Some(value) = read_uint(bits, start + i * 6, 6) else {
+            break;
+        };
+        let value = value as u8;
+        let ascii = if value < 32 { value + 64 } else { value };
+        s.push(ascii as char);
+    }
+    s.trim_end_matches('@').trim_end().to_string()
+}
+
+fn decode_position_report(bits: &[bool], message_type: u8) -> Result<AisMessage> {
+    let mmsi = read_uint(bits, 8, 30).ok_or_else(|| anyhow!("AIS message too short for MMSI"))? as u32;
+
+    let speed_raw = read_uint(bits, 50, 10).unwrap_or(1023);
+    let speed_over_ground = (speed_raw != 1023).then(|| speed_raw as f64 / 10.0);
+
+    let longitude_raw = read_int(bits, 61, 28).unwrap_or(0x6791AC0);
+    let longitude = (longitude_raw != 0x6791AC0).then(|| longitude_raw as f64 / 600_000.0);
+
+    let latitude_raw = read_int(bits, 89, 27).unwrap_or(0x3412140);
+    let latitude = (latitude_raw != 0x3412140).then(|| latitude_raw as f64 / 600_000.0);
+
+    let course_raw = read_uint(bits, 116, 12).unwrap_or(3600);
+    let course_over_ground = (course_raw != 3600).then(|| course_raw as f64 / 10.0);
+
+    let heading_raw = read_uint(bits, 128, 9).unwrap_or(511);
+    let true_heading = (heading_raw != 511).then_some(heading_raw as u16);
+
+    Ok(AisMessage::PositionReport(AisPositionReport {
+        message_type,
+        mmsi,
+        latitude,
+        longitude,
+        speed_over_ground,
+        course_over_ground,
+        true_heading,
+    }))
+}
+
+fn decode_static_data(bits: &[bool]) -> Result<AisMessage> {
+    let mmsi = read_uint(bits, 8, 30).ok_or_else(|| anyhow!("AIS message too short for MMSI"))? as u32;
+    let ship_type = read_uint(bits, 232, 8).unwrap_or(0) as u8;
+    let vessel_name = read_6bit_string(bits, 112, 20);
+
+    Ok(AisMessage::StaticData(AisStaticData {
+        mmsi,
+        vessel_name,
+        ship_type,
+    }))
+}
+
+/// Decodes one fully-reassembled AIS payload into a [`AisMessage`].
+fn decode_payload(payload: &str) -> Result<AisMessage> {
+    let bits = unarmor_payload(payload)?;
+    let message_type = read_uint(&bits, 0, 6).ok_or_else(|| anyhow!("empty AIS payload"))? as u8;
+
+    match message_type {
+        1..=3 => decode_position_report(&bits, message_type),
+        5 => decode_static_data(&bits),
+        other => Err(anyhow!("unsupported AIS message type {other}")),
+    }
+}
+
+/// Reassembles fragmented `!AIVDM`/`!AIVDO` sentences and decodes each
+/// complete message.
+#[derive(Debug, Default)]
+pub struct AisDecoder {
+    pending: HashMap<(String, u8), Vec<Option<String>>>,
+}
+
+impl AisDecoder {
+    /// Creates an empty decoder.
+    pub fn new() -> Self {
+        AisDecoder::default()
+    }
+
+    /// Feeds one `!AIVDM`/`!AIVDO` sentence into the decoder.
+    ///
+    /// Returns `Ok(Some(message))` once the sentence completes a message
+    /// (either a single-fragment sentence, or the last fragment of a
+    /// multi-fragment one), `Ok(None)` while a multi-fragment message is
+    /// still being assembled, and `Err` for a malformed or unsupported
+    /// sentence.
+    pub fn feed(&mut self, sentence: &str) -> Result<Option<AisMessage>> {
+        let sentence = sentence.trim();
+        if !verify_checksum(sentence) {
+            return Err(anyhow!("AIS sentence failed checksum: {sentence}"));
+        }
+
+        let body = sentence.split_once('*').map(|(b, _)| b).unwrap_or(sentence);
+        let Some((sentence_type, rest)) = body.split_once(',') else {
+            return Err(anyhow!("malformed AIS sentence: {sentence}"));
+        };
+        if !matches!(sentence_type.trim_start_matches('!'), "AIVDM" | "AIVDO") {
+            return Err(anyhow!("not an AIS sentence: {sentence}"));
+        }
+
+        let fields: Vec<&str> = rest.split(',').collect();
+        let total_fragments: u8 = fields
+            .first()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| anyhow!("missing AIS fragment count"))?;
+        let fragment_number: u8 = fields
+            .get(1)
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| anyhow!("missing AIS fragment number"))?;
+        let sequence_id = fields.get(2).copied().unwrap_or("").to_string();
+        let channel = fields.get(3).copied().unwrap_or("").to_string();
+        let payload = fields.get(4).copied().unwrap_or("");
+
+        if total_fragments <= 1 {
+            return decode_payload(payload).map(Some);
+        }
+
+        if fragment_number == 0 || fragment_number > total_fragments {
+            return Err(anyhow!("AIS fragment number out of range: {sentence}"));
+        }
+
+        let key = (format!("{sequence_id}:{channel}"), total_fragments);
+        let slots = self
+            .pending
+            .entry(key.clone())
+            .or_insert_with(|| vec![None; total_fragments as usize]);
+
+        if let Some(slot) = slots.get_mut(fragment_number as usize - 1) {
+            *slot = Some(payload.to_string());
+        }
+
+        if slots.iter().all(Option::is_some) {
+            let combined: String = slots.iter().flatten().cloned().collect();
+            self.pending.remove(&key);
+            return decode_payload(&combined).map(Some);
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_uint(bits: &mut [bool], start: usize, len: usize, value: u64) {
+        for i in 0..len {
+            bits[start + i] = (value >> (len - 1 - i)) & 1 == 1;
+        }
+    }
+
+    fn set_int(bits: &mut [bool], start: usize, len: usize, value: i64) {
+        let mask = (1u64 << len) - 1;
+        set_uint(bits, start, len, (value as u64) & mask);
+    }
+
+    fn armor_payload(bits: &[bool]) -> String {
+        bits.chunks(6)
+            .map(|chunk| {
+                let value = chunk.iter().fold(0u8, |acc, &b| (acc << 1) | b as u8);
+                // Inverse of `unarmor_char`.
+                (if value < 40 { value + 48 } else { value + 56 }) as char
+            })
+            .collect()
+    }
+
+    fn sentence(total: u8, frag: u8, seq: &str, channel: &str, payload: &str, fill_bits: u8) -> String {
+        let body = format!("AIVDM,{total},{frag},{seq},{channel},{payload},{fill_bits}");
+        let checksum = body.bytes().fold(0u8, |acc, b| acc ^ b);
+        format!("!{body}*{checksum:02X}")
+    }
+
+    /// Encodes a vessel name into 6-bit AIS characters, padding with `@` (0)
+    /// up to `num_chars`, mirroring `read_6bit_string`'s decoding.
+    fn set_name(bits: &mut [bool], start: usize, num_chars: usize, name: &str) {
+        for (i, c) in name.bytes().chain(std::iter::repeat(b'@')).take(num_chars).enumerate() {
+            let value = if c == b'@' { 0 } else { c - 64 };
+            set_uint(bits, start + i * 6, 6, value as u64);
+        }
+    }
+
+    #[test]
+    fn decodes_a_type_1_position_report() {
+        let mut bits = vec![false; 168];
+        set_uint(&mut bits, 0, 6, 1); // message type
+        set_uint(&mut bits, 8, 30, 123_456_789); // mmsi
+        set_uint(&mut bits, 50, 10, 80); // speed over ground: 8.0 knots
+        set_int(&mut bits, 61, 28, 6_000_000); // longitude: 10.0 degrees
+        set_int(&mut bits, 89, 27, 27_000_000); // latitude: 45.0 degrees
+        set_uint(&mut bits, 116, 12, 900); // course over ground: 90.0 degrees
+        set_uint(&mut bits, 128, 9, 88); // true heading
+
+        let payload = armor_payload(&bits);
+        let line = sentence(1, 1, "", "A", &payload, 0);
+
+        let mut decoder = AisDecoder::new();
+        let message = decoder.feed(&line).unwrap().unwrap();
+
+        let AisMessage::PositionReport(report) = message else {
+            panic!("expected a position report, got {message:?}");
+        };
+        assert_eq!(report.message_type, 1);
+        assert_eq!(report.mmsi, 123_456_789);
+        assert_eq!(report.speed_over_ground, Some(8.0));
+        assert!((report.longitude.unwrap() - 10.0).abs() < 1e-6);
+        assert!((report.latitude.unwrap() - 45.0).abs() < 1e-6);
+        assert_eq!(report.course_over_ground, Some(90.0));
+        assert_eq!(report.true_heading, Some(88));
+    }
+
+    #[test]
+    fn decodes_a_type_5_static_data_report() {
+        let mut bits = vec![false; 240];
+        set_uint(&mut bits, 0, 6, 5); // message type
+        set_uint(&mut bits, 8, 30, 987_654_321); // mmsi
+        set_name(&mut bits, 112, 20, "TESTSHIP");
+        set_uint(&mut bits, 232, 8, 70); // ship type
+
+        let payload = armor_payload(&bits);
+        let line = sentence(1, 1, "", "B", &payload, 0);
+
+        let mut decoder = AisDecoder::new();
+        let message = decoder.feed(&line).unwrap().unwrap();
+
+        let AisMessage::StaticData(data) = message else {
+            panic!("expected static data, got {message:?}");
+        };
+        assert_eq!(data.mmsi, 987_654_321);
+        assert_eq!(data.vessel_name, "TESTSHIP");
+        assert_eq!(data.ship_type, 70);
+    }
+
+    #[test]
+    fn reassembles_a_two_fragment_message_before_decoding() {
+        let mut bits = vec![false; 240];
+        set_uint(&mut bits, 0, 6, 5);
+        set_uint(&mut bits, 8, 30, 987_654_321);
+        set_name(&mut bits, 112, 20, "TESTSHIP");
+        set_uint(&mut bits, 232, 8, 70);
+
+        let payload = armor_payload(&bits);
+        let (first_half, second_half) = payload.split_at(payload.len() / 2);
+
+        let mut decoder = AisDecoder::new();
+        let first = sentence(2, 1, "9", "B", first_half, 0);
+        let second = sentence(2, 2, "9", "B", second_half, 0);
+
+        assert!(decoder.feed(&first).unwrap().is_none());
+        let message = decoder.feed(&second).unwrap().unwrap();
+
+        let AisMessage::StaticData(data) = message else {
+            panic!("expected static data, got {message:?}");
+        };
+        assert_eq!(data.mmsi, 987_654_321);
+        assert_eq!(data.vessel_name, "TESTSHIP");
+    }
+
+    #[test]
+    fn feed_rejects_a_sentence_with_a_bad_checksum() {
+        let mut decoder = AisDecoder::new();
+        assert!(decoder.feed("!AIVDM,1,1,,A,15M67FC,0*00").is_err());
+    }
+}
@@ -0,0 +1,382 @@
+//! Typed, checksum-verified parsing of NMEA GPS sentences.
+//!
+//! Unlike [`crate::parse_gps_data`], which returns raw string fields for
+//! backward compatibility, [`parse_gps_data_typed`] validates each sentence's
+//! checksum and decodes its fields into proper Rust types and SI units.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::str;
+
+/// A single GPS fix, aggregated from the typed sentences of one complete
+/// message. Fields are `None` when the corresponding sentence was missing,
+/// unfixed, or failed its checksum check.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GpsData {
+    /// Combined UTC date and time of the fix.
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Latitude in decimal degrees, positive north.
+    pub latitude: Option<f64>,
+    /// Longitude in decimal degrees, positive east.
+    pub longitude: Option<f64>,
+    /// Altitude above mean sea level, in meters.
+    pub altitude: Option<f64>,
+    /// Number of satellites used in the fix.
+    pub num_of_satellites: Option<u8>,
+    /// Horizontal dilution of precision.
+    pub hdop: Option<f64>,
+    /// Position dilution of precision.
+    pub pdop: Option<f64>,
+    /// Vertical dilution of precision.
+    pub vdop: Option<f64>,
+    /// Speed over ground, in knots.
+    pub speed_knots: Option<f64>,
+    /// Speed over ground, in kilometers per hour.
+    pub speed_kmph: Option<f64>,
+    /// Track angle, in degrees true.
+    pub track_degrees_true: Option<f64>,
+    /// `true` when the receiver reports a valid fix (`GNGGA` fix quality > 0
+    /// or `GNRMC`/`GNGLL` status `A`).
+    pub has_fix: bool,
+    /// Satellites in view, merged across every `GSV` sentence in the message.
+    pub satellites: Vec<SatelliteInView>,
+    /// Count of satellites in [`Self::satellites`] with a nonzero SNR, i.e.
+    /// satellites actually being tracked rather than merely in view.
+    pub satellites_with_signal: usize,
+}
+
+/// The GNSS constellation a satellite belongs to, taken from its GSV
+/// sentence's talker ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Constellation {
+    /// `GPGSV` - United States GPS.
+    Gps,
+    /// `BDGSV` - Chinese BeiDou.
+    BeiDou,
+    /// Any other or future talker ID, kept verbatim (e.g. `GL` for GLONASS,
+    /// `GA` for Galileo).
+    Other(String),
+}
+
+impl Constellation {
+    fn from_talker(talker: &str) -> Self {
+        match talker {
+            "GP" => Constellation::Gps,
+            "BD" | "GB" => Constellation::BeiDou,
+            other => Constellation::Other(other.to_string()),
+        }
+    }
+}
+
+/// One satellite reported by a `GSV` sentence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SatelliteInView {
+    /// Which constellation this satellite belongs to.
+    pub constellation: Constellation,
+    /// The satellite's PRN (pseudo-random noise) number.
+    pub prn: u16,
+    /// Elevation above the horizon, in degrees (0-90).
+    pub elevation_deg: Option<u8>,
+    /// Azimuth, in degrees true (0-359).
+    pub azimuth_deg: Option<u16>,
+    /// Signal-to-noise ratio (C/N0), in dB-Hz. `None` if the satellite is in
+    /// view but not being tracked.
+    pub snr_db: Option<u8>,
+}
+
+/// Parses the up-to-four satellites carried by one `GSV` sentence.
+fn parse_gsv_satellites(talker: &str, fields: &[&str]) -> Vec<SatelliteInView> {
+    let constellation = Constellation::from_talker(talker);
+    fields
+        .get(3..)
+        .unwrap_or(&[])
+        .chunks(4)
+        .filter_map(|chunk| {
+            let prn: u16 = parse_field(chunk.first().copied().unwrap_or(""))?;
+            Some(SatelliteInView {
+                constellation: constellation.clone(),
+                prn,
+                elevation_deg: chunk.get(1).copied().and_then(parse_field),
+                azimuth_deg: chunk.get(2).copied().and_then(parse_field),
+                snr_db: chunk.get(3).copied().and_then(parse_field),
+            })
+        })
+        .collect()
+}
+
+/// Verifies the checksum of a single NMEA sentence.
+///
+/// The checksum is the XOR of every byte between `$` (or `!`) and `*`,
+/// compared against the two hex digits that follow `*`. Returns `false` if
+/// the sentence has no `*` delimiter or the digits don't match.
+pub fn verify_checksum(sentence: &str) -> bool {
+    let sentence = sentence.trim();
+    let Some(body_start) = sentence.find(['$', '!']) else {
+        return false;
+    };
+    let Some(star) = sentence.find('*') else {
+        return false;
+    };
+    if star < body_start || sentence.len() < star + 3 {
+        return false;
+    }
+
+    let body = &sentence[body_start + 1..star];
+    let expected = body.bytes().fold(0u8, |acc, b| acc ^ b);
+
+    u8::from_str_radix(&sentence[star + 1..star + 3], 16)
+        .map(|actual| actual == expected)
+        .unwrap_or(false)
+}
+
+/// Converts a NMEA `ddmm.mmmm` coordinate and hemisphere letter into decimal
+/// degrees, negated for `S`/`W`.
+fn coordinate_to_decimal_degrees(raw: &str, hemisphere: &str) -> Option<f64> {
+    if raw.is_empty() {
+        return None;
+    }
+    let value: f64 = raw.parse().ok()?;
+    let degrees = (value / 100.0).floor();
+    let minutes = value - degrees * 100.0;
+    let decimal = degrees + minutes / 60.0;
+
+    Some(match hemisphere {
+        "S" | "W" => -decimal,
+        _ => decimal,
+    })
+}
+
+/// Combines a NMEA UTC time (`hhmmss.ss`) and date (`ddmmyy`) field into a
+/// single `DateTime<Utc>`.
+fn combine_utc_timestamp(time: &str, date: &str) -> Option<DateTime<Utc>> {
+    if time.is_empty() || date.is_empty() {
+        return None;
+    }
+    let time_part = time.split('.').next().unwrap_or(time);
+    let naive_time = NaiveTime::parse_from_str(time_part, "%H%M%S").ok()?;
+    let naive_date = NaiveDate::parse_from_str(date, "%d%m%y").ok()?;
+
+    Some(DateTime::from_naive_utc_and_offset(
+        NaiveDateTime::new(naive_date, naive_time),
+        Utc,
+    ))
+}
+
+fn parse_field<T: str::FromStr>(field: &str) -> Option<T> {
+    if field.is_empty() {
+        None
+    } else {
+        field.parse().ok()
+    }
+}
+
+/// Parses GPS data from a complete NMEA message into a typed [`GpsData`].
+///
+/// Each sentence's checksum is verified before its fields are decoded;
+/// sentences that fail the check, or whose `*` checksum delimiter is
+/// missing, are skipped rather than aborting the whole message.
+///
+/// # Arguments
+///
+/// * `nmea_data` - A slice of bytes containing one or more NMEA sentences
+///
+/// # Example
+///
+/// ```rust
+/// use yahboom_gps::gps_data::parse_gps_data_typed;
+/// let nmea_data = b"$GNGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*59";
+/// let fix = parse_gps_data_typed(nmea_data).unwrap();
+/// assert!((fix.latitude.unwrap() - 48.1173).abs() < 1e-3);
+/// ```
+pub fn parse_gps_data_typed(nmea_data: &[u8]) -> Result<GpsData> {
+    let data_str = str::from_utf8(nmea_data).map_err(|e| anyhow!("invalid UTF-8: {e}"))?;
+    let mut fix = GpsData::default();
+
+    for line in data_str.lines() {
+        let line = line.trim();
+        if line.is_empty() || !verify_checksum(line) {
+            continue;
+        }
+
+        let Some((sentence_type, rest)) = line.split_once(',') else {
+            continue;
+        };
+        let sentence_type = sentence_type.trim_start_matches('$');
+        let body = rest.split_once('*').map(|(b, _)| b).unwrap_or(rest);
+        let fields: Vec<&str> = body.split(',').collect();
+        let get = |i: usize| fields.get(i).copied().unwrap_or("");
+
+        match sentence_type {
+            "GNGGA" => {
+                fix.latitude = coordinate_to_decimal_degrees(get(1), get(2));
+                fix.longitude = coordinate_to_decimal_degrees(get(3), get(4));
+                fix.num_of_satellites = parse_field(get(6));
+                fix.hdop = parse_field(get(7));
+                fix.altitude = parse_field(get(8));
+                if parse_field::<u8>(get(5)).unwrap_or(0) > 0 {
+                    fix.has_fix = true;
+                }
+            }
+            "GNRMC" => {
+                fix.latitude = fix.latitude.or_else(|| coordinate_to_decimal_degrees(get(2), get(3)));
+                fix.longitude = fix.longitude.or_else(|| coordinate_to_decimal_degrees(get(4), get(5)));
+                fix.speed_knots = parse_field(get(6));
+                fix.timestamp = combine_utc_timestamp(get(0), get(8));
+                if get(1) == "A" {
+                    fix.has_fix = true;
+                }
+            }
+            "GNGLL" => {
+                fix.latitude = fix.latitude.or_else(|| coordinate_to_decimal_degrees(get(0), get(1)));
+                fix.longitude = fix.longitude.or_else(|| coordinate_to_decimal_degrees(get(2), get(3)));
+                if get(5) == "A" {
+                    fix.has_fix = true;
+                }
+            }
+            "GPGSA" | "BDGSA" => {
+                fix.pdop = fix.pdop.or_else(|| parse_field(get(14)));
+                fix.hdop = fix.hdop.or_else(|| parse_field(get(15)));
+                fix.vdop = fix.vdop.or_else(|| parse_field(get(16)));
+            }
+            "GNVTG" => {
+                fix.track_degrees_true = parse_field(get(0));
+                fix.speed_knots = fix.speed_knots.or_else(|| parse_field(get(4)));
+                fix.speed_kmph = parse_field(get(6));
+            }
+            t if t.ends_with("GSV") => {
+                let talker = &sentence_type[..sentence_type.len() - 3];
+                fix.satellites.extend(parse_gsv_satellites(talker, &fields));
+            }
+            _ => {}
+        }
+    }
+
+    fix.satellites_with_signal = fix
+        .satellites
+        .iter()
+        .filter(|sat| sat.snr_db.is_some_and(|snr| snr > 0))
+        .count();
+
+    Ok(fix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_checksum_accepts_a_valid_sentence() {
+        assert!(verify_checksum(
+            "$GNGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*59"
+        ));
+    }
+
+    #[test]
+    fn verify_checksum_rejects_a_mismatched_checksum() {
+        assert!(!verify_checksum(
+            "$GNGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47"
+        ));
+    }
+
+    #[test]
+    fn verify_checksum_rejects_a_missing_delimiter() {
+        assert!(!verify_checksum("$GNGGA,123519,4807.038,N"));
+    }
+
+    #[test]
+    fn coordinate_to_decimal_degrees_converts_north_and_east_as_positive() {
+        let latitude = coordinate_to_decimal_degrees("4807.038", "N").unwrap();
+        assert!((latitude - 48.1173).abs() < 1e-3);
+
+        let longitude = coordinate_to_decimal_degrees("01131.000", "E").unwrap();
+        assert!((longitude - 11.5167).abs() < 1e-3);
+    }
+
+    #[test]
+    fn coordinate_to_decimal_degrees_negates_south_and_west() {
+        let latitude = coordinate_to_decimal_degrees("4807.038", "S").unwrap();
+        assert!((latitude + 48.1173).abs() < 1e-3);
+
+        let longitude = coordinate_to_decimal_degrees("01131.000", "W").unwrap();
+        assert!((longitude + 11.5167).abs() < 1e-3);
+    }
+
+    #[test]
+    fn coordinate_to_decimal_degrees_is_none_for_an_empty_field() {
+        assert_eq!(coordinate_to_decimal_degrees("", "N"), None);
+    }
+
+    #[test]
+    fn constellation_from_talker_recognizes_gps_and_beidou() {
+        assert_eq!(Constellation::from_talker("GP"), Constellation::Gps);
+        assert_eq!(Constellation::from_talker("BD"), Constellation::BeiDou);
+        assert_eq!(Constellation::from_talker("GB"), Constellation::BeiDou);
+    }
+
+    #[test]
+    fn constellation_from_talker_keeps_unknown_talkers_verbatim() {
+        assert_eq!(
+            Constellation::from_talker("GL"),
+            Constellation::Other("GL".to_string())
+        );
+        assert_eq!(
+            Constellation::from_talker("GA"),
+            Constellation::Other("GA".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_gsv_satellites_decodes_up_to_four_satellites() {
+        let fields: Vec<&str> = "3,1,11,10,03,111,17,04,08,280,25,06,53,070,30,13,30,303,29"
+            .split(',')
+            .collect();
+        let satellites = parse_gsv_satellites("GP", &fields);
+
+        assert_eq!(satellites.len(), 4);
+        assert_eq!(
+            satellites[0],
+            SatelliteInView {
+                constellation: Constellation::Gps,
+                prn: 10,
+                elevation_deg: Some(3),
+                azimuth_deg: Some(111),
+                snr_db: Some(17),
+            }
+        );
+        assert_eq!(satellites[3].prn, 13);
+        assert_eq!(satellites[3].snr_db, Some(29));
+    }
+
+    #[test]
+    fn parse_gsv_satellites_handles_a_satellite_with_no_snr() {
+        let fields: Vec<&str> = "1,1,04,21,39,202,".split(',').collect();
+        let satellites = parse_gsv_satellites("BD", &fields);
+
+        assert_eq!(satellites.len(), 1);
+        assert_eq!(satellites[0].constellation, Constellation::BeiDou);
+        assert_eq!(satellites[0].prn, 21);
+        assert_eq!(satellites[0].elevation_deg, Some(39));
+        assert_eq!(satellites[0].azimuth_deg, Some(202));
+        assert_eq!(satellites[0].snr_db, None);
+    }
+
+    #[test]
+    fn parse_gps_data_typed_merges_gsv_sentences_from_any_talker() {
+        let gp = b"$GPGSV,1,1,01,10,03,111,17*4D\r\n";
+        let gl = b"$GLGSV,1,1,01,65,24,045,22*50\r\n";
+        let mut message = gp.to_vec();
+        message.extend_from_slice(gl);
+
+        let fix = parse_gps_data_typed(&message).unwrap();
+
+        assert_eq!(fix.satellites.len(), 2);
+        assert!(fix
+            .satellites
+            .iter()
+            .any(|sat| sat.constellation == Constellation::Gps));
+        assert!(fix
+            .satellites
+            .iter()
+            .any(|sat| sat.constellation == Constellation::Other("GL".to_string())));
+    }
+}
@@ -115,6 +115,13 @@ use std::io::Read;
 use std::str;
 use std::time::Duration;
 
+pub mod ais;
+pub mod gps_data;
+pub mod pmtk;
+pub mod stream;
+
+pub use stream::{GpsPortExt, GpsStream, GpsStreamError, ParsedMessage};
+
 /// Initializes the GPS module
 ///
 /// # Arguments
@@ -0,0 +1,227 @@
+//! PMTK command support for configuring the Yahboom GPS module at runtime.
+//!
+//! PMTK is MediaTek's proprietary NMEA-like command set. A PMTK sentence has
+//! the form `$PMTK<packet_type>,<arg>,<arg>...*<checksum>\r\n`, where the
+//! checksum is the XOR of every byte between `$` and `*`, rendered as two
+//! uppercase hex digits. The module acknowledges most commands with a
+//! `$PMTK001,<packet_type>,<flag>` sentence.
+
+use anyhow::{anyhow, Result};
+use serialport::SerialPort;
+use std::io::Write;
+
+/// The result flag carried by a `$PMTK001` acknowledgement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AckFlag {
+    /// The command could not be recognized.
+    Invalid,
+    /// The command is recognized but not supported by this module.
+    Unsupported,
+    /// The command was valid but failed to execute.
+    Failed,
+    /// The command executed successfully.
+    Success,
+}
+
+impl AckFlag {
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "0" => Some(AckFlag::Invalid),
+            "1" => Some(AckFlag::Unsupported),
+            "2" => Some(AckFlag::Failed),
+            "3" => Some(AckFlag::Success),
+            _ => None,
+        }
+    }
+}
+
+/// A builder for PMTK command sentences that computes the checksum
+/// automatically.
+///
+/// # Example
+///
+/// ```rust
+/// use yahboom_gps::pmtk::PmtkCommand;
+/// let sentence = PmtkCommand::new(220).arg(1000).build();
+/// assert_eq!(sentence, b"$PMTK220,1000*1F\r\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PmtkCommand {
+    packet_type: u32,
+    args: Vec<String>,
+}
+
+impl PmtkCommand {
+    /// Starts a new command for the given PMTK packet type.
+    pub fn new(packet_type: u32) -> Self {
+        PmtkCommand {
+            packet_type,
+            args: Vec::new(),
+        }
+    }
+
+    /// Appends an argument to the command.
+    pub fn arg(mut self, value: impl ToString) -> Self {
+        self.args.push(value.to_string());
+        self
+    }
+
+    /// Renders the command into the final `$PMTK...*XX\r\n` sentence bytes.
+    pub fn build(self) -> Vec<u8> {
+        let mut body = format!("PMTK{}", self.packet_type);
+        for arg in &self.args {
+            body.push(',');
+            body.push_str(arg);
+        }
+
+        let checksum = body.bytes().fold(0u8, |acc, b| acc ^ b);
+
+        let mut sentence = String::with_capacity(body.len() + 6);
+        sentence.push('$');
+        sentence.push_str(&body);
+        sentence.push('*');
+        sentence.push_str(&format!("{:02X}", checksum));
+        sentence.push_str("\r\n");
+
+        sentence.into_bytes()
+    }
+}
+
+/// Sets the position-fix update rate, in milliseconds (`PMTK220`).
+pub fn set_fix_update_rate(milliseconds: u32) -> Vec<u8> {
+    PmtkCommand::new(220).arg(milliseconds).build()
+}
+
+/// Selects which NMEA sentences are emitted and at what cadence (`PMTK314`).
+///
+/// `rates` holds the 19 output-mask fields in the order defined by the PMTK
+/// protocol (GLL, RMC, VTG, GGA, GSA, GSV, GRS, GST, then the reserved and
+/// proprietary slots), where each value is the number of position fixes
+/// between emissions of that sentence (`0` disables it, `1` emits every fix).
+pub fn set_nmea_output_rates(rates: &[u32; 19]) -> Vec<u8> {
+    let mut cmd = PmtkCommand::new(314);
+    for rate in rates {
+        cmd = cmd.arg(rate);
+    }
+    cmd.build()
+}
+
+/// The PMTK packet types that reboot the module instead of sending a
+/// `$PMTK001` acknowledgement. [`send_command`] returns [`AckFlag::Success`]
+/// for these without waiting on the serial port.
+const NO_ACK_PACKET_TYPES: [u32; 4] = [101, 102, 103, 104];
+
+/// Performs a hot restart, keeping ephemeris, time, position and almanac
+/// (`PMTK101`). The module reboots immediately rather than sending a
+/// `$PMTK001` ack; see [`send_command`].
+pub fn hot_restart() -> Vec<u8> {
+    PmtkCommand::new(101).build()
+}
+
+/// Performs a warm restart, discarding ephemeris but keeping other data
+/// (`PMTK102`). The module reboots immediately rather than sending a
+/// `$PMTK001` ack; see [`send_command`].
+pub fn warm_restart() -> Vec<u8> {
+    PmtkCommand::new(102).build()
+}
+
+/// Performs a cold restart, discarding all aiding data except the system
+/// configuration (`PMTK103`). The module reboots immediately rather than
+/// sending a `$PMTK001` ack; see [`send_command`].
+pub fn cold_restart() -> Vec<u8> {
+    PmtkCommand::new(103).build()
+}
+
+/// Performs a full cold restart, discarding all aiding data and resetting the
+/// system configuration to its factory defaults (`PMTK104`). The module
+/// reboots immediately rather than sending a `$PMTK001` ack; see
+/// [`send_command`].
+pub fn full_cold_restart() -> Vec<u8> {
+    PmtkCommand::new(104).build()
+}
+
+/// Resets the module to its factory defaults. This is the same command as
+/// [`full_cold_restart`]; it is provided under its own name because Yahboom's
+/// documentation refers to `PMTK104` as the factory reset command. The
+/// module reboots immediately rather than sending a `$PMTK001` ack; see
+/// [`send_command`].
+pub fn factory_reset() -> Vec<u8> {
+    full_cold_restart()
+}
+
+/// Changes the UART baud rate (`PMTK251`).
+pub fn set_baud_rate(baud: u32) -> Vec<u8> {
+    PmtkCommand::new(251).arg(baud).build()
+}
+
+/// Sends a PMTK command and waits for its `$PMTK001` acknowledgement.
+///
+/// The restart commands (`PMTK101`-`PMTK104`, i.e. [`hot_restart`],
+/// [`warm_restart`], [`cold_restart`], [`full_cold_restart`] and
+/// [`factory_reset`]) reboot the module instead of acknowledging, so this
+/// function writes them and returns [`AckFlag::Success`] immediately rather
+/// than blocking on a `$PMTK001` sentence that will never arrive.
+///
+/// # Arguments
+///
+/// * `port` - A mutable reference to the serial port
+/// * `command` - The raw command bytes, as produced by [`PmtkCommand::build`]
+///   or one of the convenience functions in this module
+///
+/// # Returns
+///
+/// A `Result` containing the [`AckFlag`] reported by the module.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use yahboom_gps::{gps_init, pmtk};
+///
+/// let mut port = gps_init("COM3", 9600).unwrap();
+/// let flag = pmtk::send_command(&mut port, &pmtk::set_fix_update_rate(200)).unwrap();
+/// println!("PMTK220 acknowledged as {:?}", flag);
+/// ```
+pub fn send_command(port: &mut Box<dyn SerialPort>, command: &[u8]) -> Result<AckFlag> {
+    port.write_all(command)?;
+    port.flush()?;
+
+    let expected_type = str::parse::<u32>(
+        str::from_utf8(command)
+            .unwrap_or_default()
+            .trim_start_matches("$PMTK")
+            .split([',', '*'])
+            .next()
+            .unwrap_or_default(),
+    )
+    .unwrap_or_default();
+
+    if NO_ACK_PACKET_TYPES.contains(&expected_type) {
+        return Ok(AckFlag::Success);
+    }
+
+    let mut buffer = vec![0; 256];
+    let mut data_accumulator = Vec::new();
+
+    loop {
+        let bytes = port.read(&mut buffer)?;
+        if bytes == 0 {
+            continue;
+        }
+        data_accumulator.extend_from_slice(&buffer[..bytes]);
+
+        while let Some(index) = data_accumulator.iter().position(|&x| x == b'\n') {
+            let line = String::from_utf8_lossy(&data_accumulator[..index + 1]).to_string();
+            data_accumulator.drain(..index + 1);
+
+            if let Some(rest) = line.trim().strip_prefix("$PMTK001,") {
+                let fields: Vec<&str> = rest.split([',', '*']).collect();
+                let ack_type: u32 = fields.first().and_then(|s| s.parse().ok()).unwrap_or(0);
+                let flag = fields.get(1).and_then(|s| AckFlag::from_code(s));
+
+                if ack_type == expected_type {
+                    return flag.ok_or_else(|| anyhow!("malformed PMTK001 acknowledgement: {line}"));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Iterator-based streaming API over parsed GPS fixes and AIS messages.
+
+use crate::ais::{AisDecoder, AisMessage};
+use crate::gps_data::{parse_gps_data_typed, GpsData};
+use serialport::SerialPort;
+use std::fmt;
+use std::io::Read;
+
+/// One message decoded from a [`GpsStream`]: either an own-ship GNSS fix or
+/// a nearby vessel's AIS report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedMessage {
+    /// A GNSS fix aggregated from `$`-prefixed NMEA sentences.
+    Gnss(GpsData),
+    /// A decoded `!AIVDM`/`!AIVDO` AIS message.
+    Ais(AisMessage),
+}
+
+/// An error yielded from a [`GpsStream`].
+///
+/// [`GpsStreamError::Timeout`] is non-fatal: the stream keeps reading on the
+/// next call to `next()` rather than ending, so a slow fix doesn't terminate
+/// iteration.
+#[derive(Debug)]
+pub enum GpsStreamError {
+    /// The serial port's read timed out before a complete message arrived.
+    Timeout,
+    /// Reading from the serial port failed.
+    Io(std::io::Error),
+    /// The accumulated message could not be parsed.
+    Parse(anyhow::Error),
+}
+
+impl fmt::Display for GpsStreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpsStreamError::Timeout => write!(f, "timed out waiting for a GPS message"),
+            GpsStreamError::Io(e) => write!(f, "error reading from serial port: {e}"),
+            GpsStreamError::Parse(e) => write!(f, "error parsing GPS message: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for GpsStreamError {}
+
+/// Wraps a `Box<dyn SerialPort>` and yields a [`ParsedMessage::Gnss`] fix per
+/// completed message and a [`ParsedMessage::Ais`] per decoded AIS message.
+///
+/// Bytes are buffered internally and split on `\r\n`, the way
+/// `BufReader::lines` splits on `\n`. A message is considered complete once a
+/// sentence whose type ends in the configured boundary suffix (`"RMC"` by
+/// default) has been accumulated, since an RMC sentence closes out one full
+/// fix cycle on the Yahboom module's default and PMTK314-configured sentence
+/// sets alike.
+pub struct GpsStream {
+    port: Box<dyn SerialPort>,
+    read_buf: [u8; 1024],
+    accumulator: Vec<u8>,
+    current_message: Vec<u8>,
+    boundary_suffix: String,
+    ais_decoder: AisDecoder,
+}
+
+impl GpsStream {
+    /// Wraps `port` in a `GpsStream` that closes out a message on each `RMC`
+    /// sentence.
+    pub fn new(port: Box<dyn SerialPort>) -> Self {
+        GpsStream {
+            port,
+            read_buf: [0; 1024],
+            accumulator: Vec::new(),
+            current_message: Vec::new(),
+            boundary_suffix: "RMC".to_string(),
+            ais_decoder: AisDecoder::new(),
+        }
+    }
+
+    /// Configures the sentence-type suffix (e.g. `"RMC"`, `"GGA"`, `"TXT"`)
+    /// that closes out a message, for modules reconfigured via PMTK314 to
+    /// emit a different sentence set.
+    pub fn with_boundary(mut self, suffix: impl Into<String>) -> Self {
+        self.boundary_suffix = suffix.into();
+        self
+    }
+}
+
+impl Iterator for GpsStream {
+    type Item = Result<ParsedMessage, GpsStreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(index) = self
+                .accumulator
+                .windows(2)
+                .position(|window| window == b"\r\n")
+            {
+                let line: Vec<u8> = self.accumulator.drain(..index + 2).collect();
+                let line_str = String::from_utf8_lossy(&line).trim().to_string();
+
+                if line_str.starts_with('!') {
+                    match self.ais_decoder.feed(&line_str) {
+                        Ok(Some(message)) => return Some(Ok(ParsedMessage::Ais(message))),
+                        Ok(None) => continue,
+                        Err(e) => return Some(Err(GpsStreamError::Parse(e))),
+                    }
+                }
+
+                let sentence_type = line_str
+                    .trim_start_matches('$')
+                    .split(',')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+
+                self.current_message.extend_from_slice(&line);
+
+                if sentence_type.ends_with(self.boundary_suffix.as_str()) {
+                    let message = std::mem::take(&mut self.current_message);
+                    return Some(
+                        parse_gps_data_typed(&message)
+                            .map(ParsedMessage::Gnss)
+                            .map_err(GpsStreamError::Parse),
+                    );
+                }
+                continue;
+            }
+
+            match self.port.read(&mut self.read_buf) {
+                Ok(0) => continue,
+                Ok(bytes) => self.accumulator.extend_from_slice(&self.read_buf[..bytes]),
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                    return Some(Err(GpsStreamError::Timeout))
+                }
+                Err(e) => return Some(Err(GpsStreamError::Io(e))),
+            }
+        }
+    }
+}
+
+/// Extension trait that adds [`GpsStream::new`] as a method on the serial
+/// port returned by [`crate::gps_init`].
+pub trait GpsPortExt {
+    /// Turns this port into a [`GpsStream`] of parsed GPS fixes and AIS
+    /// messages.
+    fn stream(self) -> GpsStream;
+}
+
+impl GpsPortExt for Box<dyn SerialPort> {
+    fn stream(self) -> GpsStream {
+        GpsStream::new(self)
+    }
+}